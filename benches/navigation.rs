@@ -0,0 +1,171 @@
+// Copyright (c) 2020-3 Richard Cook
+//
+// Permission is hereby granted, free of charge, to any person obtaining
+// a copy of this software and associated documentation files (the
+// "Software"), to deal in the Software without restriction, including
+// without limitation the rights to use, copy, modify, merge, publish,
+// distribute, sublicense, and/or sell copies of the Software, and to
+// permit persons to whom the Software is furnished to do so, subject to
+// the following conditions:
+//
+// The above copyright notice and this permission notice shall be
+// included in all copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND,
+// EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+// MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+// NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE
+// LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION
+// OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN CONNECTION
+// WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+//
+use joat_git_url::{GitUrl, HostInterner};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+const ITERATIONS: usize = 10_000;
+
+fn bench<F: FnMut()>(name: &str, mut f: F) {
+    let start_allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start_bytes = ALLOCATED_BYTES.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - start_allocations;
+    let bytes = ALLOCATED_BYTES.load(Ordering::Relaxed) - start_bytes;
+    println!(
+        "{name:<26} {:>10.0} ns/iter {:>8} allocs/iter {:>8} bytes/iter",
+        elapsed.as_nanos() as f64 / ITERATIONS as f64,
+        allocations / ITERATIONS,
+        bytes / ITERATIONS
+    );
+}
+
+fn deep_url(depth: usize) -> GitUrl {
+    let path = (0..depth)
+        .map(|i| format!("segment{i}"))
+        .collect::<Vec<_>>()
+        .join("/");
+    format!("git@github.com:{path}")
+        .parse()
+        .expect("parse failed")
+}
+
+fn retained_bytes<T, F: FnOnce() -> T>(f: F) -> (T, usize) {
+    let start = ALLOCATED_BYTES.load(Ordering::Relaxed);
+    let value = f();
+    (value, ALLOCATED_BYTES.load(Ordering::Relaxed) - start)
+}
+
+// The representation before navigation was optimised: an owned host and
+// path, with each ancestor produced by cloning both and scanning for `/`.
+// The host is only ever cloned, as it was by `pop`
+#[allow(dead_code)]
+#[derive(Clone)]
+struct ScanUrl {
+    host: String,
+    path: String,
+}
+
+fn pop_by_scanning(path: &mut String) -> bool {
+    if path.is_empty() {
+        false
+    } else {
+        match path.rfind('/') {
+            Some(pos) => path.truncate(pos),
+            None => path.clear(),
+        }
+        true
+    }
+}
+
+fn ancestors_by_cloning(url: &ScanUrl) -> impl Iterator<Item = ScanUrl> {
+    std::iter::successors(Some(url.clone()), |u| {
+        let mut temp = u.clone();
+        pop_by_scanning(&mut temp.path).then_some(temp)
+    })
+}
+
+fn main() {
+    let git_url = deep_url(64);
+    let scan_url = ScanUrl {
+        host: String::from("git@github.com"),
+        path: git_url.segments().collect::<Vec<_>>().join("/"),
+    };
+
+    // Both cases clone a whole URL in its representation before popping it
+    bench("pop_mut to root (scan)", || {
+        let mut temp = scan_url.clone();
+        while pop_by_scanning(&mut temp.path) {}
+        black_box(temp);
+    });
+
+    bench("pop_mut to root (offsets)", || {
+        let mut temp = git_url.clone();
+        while temp.pop_mut() {}
+        black_box(temp);
+    });
+
+    bench("ancestors (clone + pop)", || {
+        black_box(ancestors_by_cloning(&scan_url).count());
+    });
+
+    bench("ancestors (depth 64)", || {
+        black_box(git_url.ancestors().count());
+    });
+
+    bench("pop (depth 64)", || {
+        black_box(git_url.pop());
+    });
+
+    bench("join (depth 64)", || {
+        black_box(git_url.join("../aaa/bbb"));
+    });
+
+    let inputs = (0..ITERATIONS)
+        .map(|i| format!("git@github.com:user{}/repo{i}.git", i % 100))
+        .collect::<Vec<_>>();
+
+    let (plain, plain_bytes) = retained_bytes(|| {
+        inputs
+            .iter()
+            .map(|s| s.parse::<GitUrl>().expect("parse failed"))
+            .collect::<Vec<_>>()
+    });
+    let (interned, interned_bytes) = retained_bytes(|| {
+        let mut interner = HostInterner::new();
+        inputs
+            .iter()
+            .map(|s| GitUrl::from_str_interned(s, &mut interner).expect("parse failed"))
+            .collect::<Vec<_>>()
+    });
+    println!(
+        "{:<26} {plain_bytes:>10} bytes (plain) {interned_bytes:>10} bytes (interned)",
+        format!("parse {ITERATIONS} same-host")
+    );
+    black_box((plain, interned));
+}